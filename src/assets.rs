@@ -0,0 +1,125 @@
+//! Composing a vault's asset bundle from a mix of fungible and non-fungible assets.
+//!
+//! `VaultConfig` used to hard-code a single fungible asset. Inheritance typically
+//! covers a whole portfolio, so `VaultAssetsBuilder` lets a vault mix multiple fungible
+//! faucets and non-fungible assets into one bundle, while still catching the two
+//! mistakes that matter for an inheritance product: minting more of a given faucet's
+//! asset into the note than the owner intended, and attaching the same non-fungible
+//! asset twice.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use miden_client::{
+    account::AccountId,
+    asset::{Asset, FungibleAsset, NonFungibleAsset},
+    note::NoteAssets,
+};
+
+#[derive(Debug)]
+pub enum VaultAssetsError {
+    /// Adding this fungible amount would exceed the per-faucet limit configured for
+    /// `faucet` via [`VaultAssetsBuilder::with_faucet_limit`].
+    FaucetLimitExceeded { faucet: AccountId, limit: u64, attempted: u64 },
+    /// The same non-fungible asset ID was added to the bundle more than once.
+    DuplicateNonFungible(AccountId),
+}
+
+impl fmt::Display for VaultAssetsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VaultAssetsError::FaucetLimitExceeded { faucet, limit, attempted } => write!(
+                f,
+                "adding this amount would bring faucet {faucet}'s total to {attempted}, over its limit of {limit}"
+            ),
+            VaultAssetsError::DuplicateNonFungible(faucet) => {
+                write!(f, "non-fungible asset from faucet {faucet} was already added to this bundle")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VaultAssetsError {}
+
+/// Builds a vault's [`NoteAssets`] bundle from an arbitrary mix of fungible and
+/// non-fungible assets, enforcing per-faucet aggregation limits and rejecting
+/// duplicate non-fungible asset IDs along the way.
+#[derive(Default)]
+pub struct VaultAssetsBuilder {
+    faucet_limits: HashMap<AccountId, u64>,
+    fungible_totals: HashMap<AccountId, u64>,
+    seen_non_fungible: HashSet<AccountId>,
+    assets: Vec<Asset>,
+}
+
+impl VaultAssetsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps the total amount of `faucet`'s asset this bundle may hold across every
+    /// `add_fungible` call.
+    pub fn with_faucet_limit(mut self, faucet: AccountId, max_amount: u64) -> Self {
+        self.faucet_limits.insert(faucet, max_amount);
+        self
+    }
+
+    pub fn add_fungible(&mut self, faucet: AccountId, amount: u64) -> Result<&mut Self, VaultAssetsError> {
+        let running_total = self.fungible_totals.get(&faucet).copied().unwrap_or(0);
+        let new_total = running_total + amount;
+        if let Some(&limit) = self.faucet_limits.get(&faucet) {
+            if new_total > limit {
+                return Err(VaultAssetsError::FaucetLimitExceeded { faucet, limit, attempted: new_total });
+            }
+        }
+
+        // Merge into this faucet's existing entry rather than pushing a second one --
+        // `NoteAssets` is keyed by faucet, so two `add_fungible` calls for the same
+        // faucet must end up as one asset with the combined amount, not two.
+        match self.assets.iter_mut().find(|asset| matches!(asset, Asset::Fungible(existing) if existing.faucet_id() == faucet)) {
+            Some(Asset::Fungible(existing)) => *existing = FungibleAsset::new(faucet, new_total).unwrap(),
+            _ => self.assets.push(Asset::Fungible(FungibleAsset::new(faucet, amount).unwrap())),
+        }
+        self.fungible_totals.insert(faucet, new_total);
+        Ok(self)
+    }
+
+    pub fn add_non_fungible(&mut self, asset: NonFungibleAsset) -> Result<&mut Self, VaultAssetsError> {
+        if !self.seen_non_fungible.insert(asset.faucet_id()) {
+            return Err(VaultAssetsError::DuplicateNonFungible(asset.faucet_id()));
+        }
+
+        self.assets.push(Asset::NonFungible(asset));
+        Ok(self)
+    }
+
+    /// Finalizes the bundle. Atomicity of the transfer itself is enforced by the note
+    /// script, which moves every asset in the returned `NoteAssets` in a single
+    /// consumption (see `masm/inheritance_vault_note.masm`).
+    pub fn build(self) -> NoteAssets {
+        NoteAssets::new(self.assets).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn faucet(seed: u8) -> AccountId {
+        AccountId::try_from([seed; 15].as_slice()).expect("test account id")
+    }
+
+    #[test]
+    fn repeated_add_fungible_merges_into_one_asset() {
+        let faucet_id = faucet(1);
+        let mut builder = VaultAssetsBuilder::new();
+        builder.add_fungible(faucet_id, 3).unwrap();
+        builder.add_fungible(faucet_id, 4).unwrap();
+
+        let assets: Vec<Asset> = builder.build().iter().copied().collect();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].unwrap_fungible().amount(), 7);
+    }
+}
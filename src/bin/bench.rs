@@ -0,0 +1,192 @@
+//! Load-test harness for the full vault lifecycle: create -> mint -> deposit -> claim,
+//! driven at a configurable claims-per-second rate via token-bucket pacing. Spins up
+//! `--accounts` owner/beneficiary pairs behind a shared faucet, creates a vault note
+//! for each, then paces the beneficiary claims at `--tps` while recording submission
+//! latency, proof-generation time, and success/failure counts into a summary report.
+//! This gives maintainers a repeatable throughput benchmark for the inheritance-vault
+//! note script under contention, instead of the single hand-wired transaction in
+//! `main.rs`.
+
+use std::{sync::Arc, time::Duration};
+
+use clap::Parser;
+use tokio::time::Instant;
+
+use miden_client::{
+    account::Account, builder::ClientBuilder, keystore::FilesystemKeyStore, note::Note,
+    rpc::{Endpoint, TonicRpcClient}, transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError,
+};
+use miden_client_tools::create_basic_account;
+
+use miden_inheritance_vaults::{
+    assets::VaultAssetsBuilder,
+    faucet::{FaucetService, MintOutcome, RateLimitConfig},
+    guardian::GuardianThreshold,
+    key_manager::KeyManager,
+    vault::{build_vault_note, VaultConfig},
+};
+
+#[derive(Parser)]
+#[command(about = "Load-test the inheritance vault note script under contention")]
+struct BenchArgs {
+    /// Number of owner/beneficiary pairs (and therefore vault notes) to drive.
+    #[arg(long, default_value_t = 10)]
+    accounts: usize,
+
+    /// Target claim submissions per second, paced with a token-bucket.
+    #[arg(long, default_value_t = 5)]
+    tps: u64,
+
+    /// Blocks from the current tip before a vault's deadline passes.
+    #[arg(long, default_value_t = 1)]
+    deadline_offset: u64,
+}
+
+struct ClaimSample {
+    proof_gen: Duration,
+    submission: Duration,
+    succeeded: bool,
+}
+
+struct TokenBucket {
+    interval: Duration,
+    next_release: Instant,
+}
+
+impl TokenBucket {
+    fn new(tps: u64) -> Self {
+        let interval = Duration::from_secs_f64(1.0 / tps.max(1) as f64);
+        Self { interval, next_release: Instant::now() }
+    }
+
+    async fn acquire(&mut self) {
+        let now = Instant::now();
+        if self.next_release > now {
+            tokio::time::sleep(self.next_release - now).await;
+        }
+        self.next_release = Instant::now() + self.interval;
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), ClientError> {
+    let args = BenchArgs::parse();
+
+    let endpoint = Endpoint::new("http".to_string(), "localhost".to_string(), Some(57291));
+    let rpc_api = Arc::new(TonicRpcClient::new(&endpoint, 10_000));
+    let mut client = ClientBuilder::new()
+        .with_rpc(rpc_api)
+        .with_filesystem_keystore("./keystore")
+        .in_debug_mode(true)
+        .build()
+        .await?;
+    client.sync_state().await?;
+
+    let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+    let (key_manager, _mnemonic) = KeyManager::generate("");
+
+    println!("Deploying bench faucet...");
+    let limits = RateLimitConfig { max_amount_per_request: u64::MAX, cooldown: Duration::from_secs(0) };
+    let mut faucet_service = FaucetService::deploy(
+        &mut client,
+        keystore.clone(),
+        key_manager.derive_key(0),
+        limits,
+        "./bench_faucet_state.json",
+    )
+    .await
+    .unwrap();
+
+    println!("Creating {} owner/beneficiary pairs and vault notes...", args.accounts);
+    let mut pairs = Vec::with_capacity(args.accounts);
+    for i in 0..args.accounts {
+        let (owner, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+        let (beneficiary, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+
+        if let MintOutcome::Rejected { reason_code, .. } =
+            faucet_service.request_mint(&mut client, &owner, 10).await.unwrap()
+        {
+            panic!("bench faucet mint unexpectedly rejected (reason {reason_code})");
+        }
+
+        let owner_key = key_manager.derive_key(100 + i as u32);
+        let sync_summary = client.sync_state().await.unwrap();
+        let deadline = sync_summary.block_num.as_u64() + args.deadline_offset;
+
+        let mut assets_builder = VaultAssetsBuilder::new();
+        assets_builder.add_fungible(faucet_service.account.id(), 10).unwrap();
+
+        let vault_config = VaultConfig {
+            owner: owner.id(),
+            beneficiary: beneficiary.id(),
+            owner_pubkey_hash: owner_key.public_key().into(),
+            guardian_threshold: GuardianThreshold::new(0, vec![]),
+            assets: assets_builder.build(),
+        };
+
+        let note = build_vault_note(&mut client, &vault_config, deadline);
+        let request = TransactionRequestBuilder::new()
+            .with_own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()
+            .unwrap();
+        let tx_result = client.new_transaction(owner.id(), request).await.unwrap();
+        client.submit_transaction(tx_result).await.unwrap();
+
+        pairs.push((beneficiary, note));
+    }
+    client.sync_state().await?;
+
+    println!("Waiting for {} deadline blocks to pass...", args.deadline_offset);
+    tokio::time::sleep(Duration::from_secs(args.deadline_offset.max(1) * 10)).await;
+
+    println!("Issuing {} claims at {} tps...", pairs.len(), args.tps);
+    let mut bucket = TokenBucket::new(args.tps);
+    let mut samples = Vec::with_capacity(pairs.len());
+    for (beneficiary, note) in pairs {
+        bucket.acquire().await;
+        samples.push(claim_one(&mut client, &beneficiary, note).await);
+    }
+
+    report(&samples);
+    Ok(())
+}
+
+async fn claim_one(client: &mut Client, beneficiary: &Account, note: Note) -> ClaimSample {
+    let proof_start = Instant::now();
+    let request = TransactionRequestBuilder::new()
+        .with_unauthenticated_input_notes([(note, None)])
+        .build()
+        .unwrap();
+    let tx_result = client.new_transaction(beneficiary.id(), request).await;
+    let proof_gen = proof_start.elapsed();
+
+    let submit_start = Instant::now();
+    let succeeded = match tx_result {
+        Ok(tx_result) => client.submit_transaction(tx_result).await.is_ok(),
+        Err(_) => false,
+    };
+    let submission = submit_start.elapsed();
+
+    ClaimSample { proof_gen, submission, succeeded }
+}
+
+fn report(samples: &[ClaimSample]) {
+    let total = samples.len();
+    let succeeded = samples.iter().filter(|s| s.succeeded).count();
+    let failed = total - succeeded;
+
+    let avg = |pick: fn(&ClaimSample) -> Duration| -> Duration {
+        if samples.is_empty() {
+            return Duration::ZERO;
+        }
+        samples.iter().map(pick).sum::<Duration>() / samples.len() as u32
+    };
+
+    println!("\n=== Bench summary ===");
+    println!("claims attempted:   {total}");
+    println!("claims succeeded:   {succeeded}");
+    println!("claims failed:      {failed}");
+    println!("avg proof-gen time: {:?}", avg(|s| s.proof_gen));
+    println!("avg submission time:{:?}", avg(|s| s.submission));
+}
@@ -0,0 +1,76 @@
+//! Long-lived rate-limited faucet service, decoupled from the one-shot demo in
+//! `main.rs`. Reads `<recipient bech32> <amount>` requests from stdin, one per line,
+//! and prints the outcome of each -- minted, or rejected with the remaining allowance
+//! encoded in a memo note. Per-recipient caps and cooldowns persist to
+//! `./faucet_state.json` across restarts, so this is safe to leave running for
+//! integration tests without draining the faucet's supply.
+
+use std::{io::BufRead, sync::Arc, time::Duration};
+
+use miden_client::{
+    account::AccountId, builder::ClientBuilder, keystore::FilesystemKeyStore,
+    rpc::{Endpoint, TonicRpcClient}, ClientError,
+};
+use miden_objects::account::NetworkId;
+
+use miden_inheritance_vaults::{
+    faucet::{FaucetService, MintOutcome, RateLimitConfig},
+    key_manager::KeyManager,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), ClientError> {
+    let endpoint = Endpoint::new("http".to_string(), "localhost".to_string(), Some(57291));
+    let rpc_api = Arc::new(TonicRpcClient::new(&endpoint, 10_000));
+
+    let mut client = ClientBuilder::new()
+        .with_rpc(rpc_api)
+        .with_filesystem_keystore("./keystore")
+        .in_debug_mode(true)
+        .build()
+        .await?;
+    client.sync_state().await?;
+
+    let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+    let (key_manager, mnemonic) = KeyManager::generate("");
+    println!("Faucet recovery phrase: {mnemonic}");
+
+    let limits = RateLimitConfig { max_amount_per_request: 1_000, cooldown: Duration::from_secs(60) };
+    let mut faucet =
+        FaucetService::deploy(&mut client, keystore, key_manager.derive_key(0), limits, "./faucet_state.json")
+            .await?;
+    println!(
+        "Faucet running. Account ID: {:?}\nEnter requests as '<recipient bech32> <amount>', one per line.",
+        faucet.account.id().to_bech32(NetworkId::Testnet)
+    );
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line.unwrap();
+        let Some((recipient_str, amount_str)) = line.trim().split_once(' ') else {
+            println!("expected '<recipient bech32> <amount>'");
+            continue;
+        };
+        let Ok(amount) = amount_str.parse::<u64>() else {
+            println!("invalid amount: {amount_str}");
+            continue;
+        };
+        let Ok((_, recipient_id)) = AccountId::from_bech32(recipient_str) else {
+            println!("invalid account id: {recipient_str}");
+            continue;
+        };
+        let Some(recipient_account) = client.get_account(recipient_id).await? else {
+            println!("unknown account: {recipient_str}");
+            continue;
+        };
+
+        match faucet.request_mint(&mut client, &recipient_account, amount).await? {
+            MintOutcome::Minted => println!("minted {amount} to {recipient_str}"),
+            MintOutcome::Rejected { reason_code, remaining_allowance, memo_note } => println!(
+                "rejected (reason {reason_code}, remaining allowance {remaining_allowance}), memo note {:?}",
+                memo_note.id().to_hex()
+            ),
+        }
+    }
+
+    Ok(())
+}
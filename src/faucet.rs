@@ -0,0 +1,202 @@
+//! Standalone rate-limited faucet service.
+//!
+//! Pulled out of the one-shot demo flow in `main.rs` so the faucet can run unattended
+//! for integration tests: every mint request is checked against a per-recipient cap
+//! and cooldown window, persisted to disk so limits survive a restart. A request that
+//! exceeds its cap is never silently dropped -- it comes back as a zero-asset "memo"
+//! note encoding the rejection reason and the recipient's remaining allowance, so
+//! callers get a structured rejection instead of a bare transaction failure.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rand::{rngs::StdRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use miden_client::{
+    account::{
+        component::{BasicFungibleFaucet, RpoFalcon512},
+        Account, AccountBuilder, AccountId, AccountStorageMode, AccountType,
+    },
+    asset::TokenSymbol,
+    auth::AuthSecretKey,
+    crypto::{FeltRng, SecretKey},
+    keystore::FilesystemKeyStore,
+    note::{
+        Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteInputs, NoteMetadata,
+        NoteRecipient, NoteScript, NoteTag, NoteType,
+    },
+    transaction::{OutputNote, TransactionKernel, TransactionRequestBuilder},
+    Client, ClientError, Felt,
+};
+use miden_client_tools::mint_from_faucet_for_account;
+use miden_objects::account::NetworkId;
+
+/// A no-op note script for memo notes: the note carries data in its inputs but moves
+/// no assets, so observing it via `sync_state` is enough -- it never needs consuming.
+const MEMO_NOTE_CODE: &str = "begin nop end";
+
+/// Reason codes encoded as the first memo-note input, mirrored on the caller side.
+pub const REASON_PER_RECIPIENT_CAP_EXCEEDED: u64 = 1;
+pub const REASON_COOLDOWN_ACTIVE: u64 = 2;
+
+/// Per-recipient limits enforced by the faucet service.
+pub struct RateLimitConfig {
+    pub max_amount_per_request: u64,
+    pub cooldown: Duration,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+struct RecipientState {
+    last_mint_unix_secs: u64,
+}
+
+/// Per-recipient cooldown state, persisted to `state_path` across restarts.
+#[derive(Serialize, Deserialize, Default)]
+struct PersistedState {
+    recipients: HashMap<String, RecipientState>,
+}
+
+impl PersistedState {
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) {
+        let contents = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+/// Outcome of a mint request against the rate-limited faucet.
+pub enum MintOutcome {
+    Minted,
+    /// The request exceeded a limit; `memo_note` is a zero-asset note, already
+    /// submitted, encoding `reason_code` and `remaining_allowance` for the caller.
+    Rejected { reason_code: u64, remaining_allowance: u64, memo_note: Note },
+}
+
+/// A long-lived faucet service decoupled from the one-shot demo flow, enforcing a
+/// per-recipient cap and cooldown persisted across restarts.
+pub struct FaucetService {
+    pub account: Account,
+    limits: RateLimitConfig,
+    state_path: PathBuf,
+    state: PersistedState,
+}
+
+impl FaucetService {
+    /// Deploys a fresh faucet account, matching the demo's former `create_basic_faucet`.
+    pub async fn deploy(
+        client: &mut Client,
+        keystore: FilesystemKeyStore<StdRng>,
+        key_pair: SecretKey,
+        limits: RateLimitConfig,
+        state_path: impl Into<PathBuf>,
+    ) -> Result<Self, ClientError> {
+        let mut init_seed = [0u8; 32];
+        client.rng().fill_bytes(&mut init_seed);
+        let anchor_block = client.get_latest_epoch_block().await.unwrap();
+        let symbol = TokenSymbol::new("INH").unwrap();
+        let decimals = 8;
+        let max_supply = Felt::new(1_000_000);
+        let builder = AccountBuilder::new(init_seed)
+            .anchor((&anchor_block).try_into().unwrap())
+            .account_type(AccountType::FungibleFaucet)
+            .storage_mode(AccountStorageMode::Public)
+            .with_component(RpoFalcon512::new(key_pair.public_key()))
+            .with_component(BasicFungibleFaucet::new(symbol, decimals, max_supply).unwrap());
+        let (account, seed) = builder.build().unwrap();
+        client.add_account(&account, Some(seed), false).await?;
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).unwrap();
+
+        let state_path = state_path.into();
+        let state = PersistedState::load(&state_path);
+        Ok(Self { account, limits, state_path, state })
+    }
+
+    fn in_cooldown(&self, now: u64, recipient: &RecipientState) -> bool {
+        now.saturating_sub(recipient.last_mint_unix_secs) < self.limits.cooldown.as_secs()
+    }
+
+    fn remaining_allowance(&self, now: u64, recipient: &RecipientState) -> u64 {
+        if self.in_cooldown(now, recipient) {
+            0
+        } else {
+            self.limits.max_amount_per_request
+        }
+    }
+
+    /// Mints `amount` to `recipient` if it fits within the per-recipient cap and
+    /// cooldown, otherwise submits a zero-asset memo note explaining the rejection.
+    pub async fn request_mint(
+        &mut self,
+        client: &mut Client,
+        recipient: &Account,
+        amount: u64,
+    ) -> Result<MintOutcome, ClientError> {
+        let recipient_key = recipient.id().to_bech32(NetworkId::Testnet);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let recipient_state = *self.state.recipients.entry(recipient_key.clone()).or_default();
+        let in_cooldown = self.in_cooldown(now, &recipient_state);
+        let allowance = self.remaining_allowance(now, &recipient_state);
+
+        if amount > allowance {
+            let reason_code = if in_cooldown { REASON_COOLDOWN_ACTIVE } else { REASON_PER_RECIPIENT_CAP_EXCEEDED };
+            let memo_note = self.submit_memo_note(client, recipient.id(), reason_code, allowance).await?;
+            return Ok(MintOutcome::Rejected { reason_code, remaining_allowance: allowance, memo_note });
+        }
+
+        mint_from_faucet_for_account(client, recipient, &self.account, amount, None).await?;
+        self.state.recipients.get_mut(&recipient_key).unwrap().last_mint_unix_secs = now;
+        self.state.save(&self.state_path);
+
+        Ok(MintOutcome::Minted)
+    }
+
+    async fn submit_memo_note(
+        &self,
+        client: &mut Client,
+        recipient: AccountId,
+        reason_code: u64,
+        remaining_allowance: u64,
+    ) -> Result<Note, ClientError> {
+        let assembler = TransactionKernel::assembler().with_debug_mode(true);
+        let note_script = NoteScript::compile(MEMO_NOTE_CODE, assembler).unwrap();
+        let note_inputs = NoteInputs::new(vec![
+            Felt::new(reason_code),
+            Felt::new(remaining_allowance),
+            recipient.suffix(),
+            recipient.prefix().as_felt(),
+        ])
+        .unwrap();
+        let serial_num = client.rng().draw_word();
+        let note_recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+        let tag = NoteTag::for_public_use_case(1, 0, NoteExecutionMode::Local).unwrap();
+        let metadata = NoteMetadata::new(
+            self.account.id(),
+            NoteType::Public,
+            tag,
+            NoteExecutionHint::always(),
+            Felt::new(0),
+        )
+        .unwrap();
+        let memo_note = Note::new(NoteAssets::new(vec![]).unwrap(), metadata, note_recipient);
+
+        let note_request = TransactionRequestBuilder::new()
+            .with_own_output_notes(vec![OutputNote::Full(memo_note.clone())])
+            .build()
+            .unwrap();
+        let tx_result = client.new_transaction(self.account.id(), note_request).await?;
+        client.submit_transaction(tx_result).await?;
+
+        Ok(memo_note)
+    }
+}
@@ -0,0 +1,108 @@
+//! M-of-N guardian attestation for early vault release.
+//!
+//! The note script accepts two independent release conditions: the deadline passing,
+//! or at least M of the N listed guardians signing off that the owner is gone.
+//! Guardian signatures are never threaded through `NoteArgs` -- `NoteArgs` is a single
+//! `Word` (four felts), nowhere near enough to carry a serialized RpoFalcon512
+//! signature, let alone several. Instead, `masm/inheritance_vault_note.masm` resolves
+//! each guardian's signature itself, the same way normal account authentication does
+//! elsewhere in this codebase: from the keystore of whichever client ends up proving
+//! the claim transaction. The script checks every one of the N configured guardians'
+//! real public keys against a signature over the note's serial number and counts how
+//! many actually verify, so "attesting" means registering a guardian's real key with
+//! that keystore before the claim is built -- not asserting a client-supplied count
+//! that nothing checks cryptographically.
+
+use miden_client::{Felt, Word};
+
+/// A vault can name at most this many guardians. `M` and `N` are small by design for an
+/// inheritance vault (a handful of trusted parties, not a DAO), and capping `N` keeps
+/// the guardian section of the note inputs a fixed, predictable size.
+pub const MAX_GUARDIANS: usize = 3;
+
+/// A guardian able to co-sign early release of a vault.
+#[derive(Clone)]
+pub struct Guardian {
+    pub public_key_hash: Word,
+}
+
+impl Guardian {
+    pub fn new(public_key_hash: Word) -> Self {
+        Self { public_key_hash }
+    }
+}
+
+/// The M-of-N guardian policy attached to a vault note.
+pub struct GuardianThreshold {
+    pub threshold: u32,
+    pub guardians: Vec<Guardian>,
+}
+
+impl GuardianThreshold {
+    pub fn new(threshold: u32, guardians: Vec<Guardian>) -> Self {
+        assert!(
+            guardians.len() <= MAX_GUARDIANS,
+            "a vault supports at most {MAX_GUARDIANS} guardians"
+        );
+        assert!(
+            threshold as usize <= guardians.len(),
+            "threshold cannot exceed the number of guardians"
+        );
+        assert!(
+            guardians.is_empty() || threshold >= 1,
+            "threshold must be at least 1 whenever guardians are configured -- M == 0 would let \
+             any beneficiary release the vault before the deadline with zero guardian involvement"
+        );
+        Self { threshold, guardians }
+    }
+
+    /// Appends this threshold's note-input words to `inputs`, which must already hold
+    /// exactly one word (`[deadline, ben_suffix, ben_prefix, M]` -- `M` lives in that
+    /// first word, not here). Appends `[N, 0, 0, 0]`, then one zero-padded `Word` per
+    /// [`MAX_GUARDIANS`] slot, matching the word-aligned layout
+    /// `masm/inheritance_vault_note.masm` reads back with `mem_loadw`.
+    pub fn extend_note_inputs(&self, mut inputs: Vec<Felt>) -> Vec<Felt> {
+        inputs.push(Felt::new(self.guardians.len() as u64));
+        inputs.extend_from_slice(&[Felt::new(0); 3]);
+        for slot in 0..MAX_GUARDIANS {
+            let hash = self
+                .guardians
+                .get(slot)
+                .map(|guardian| guardian.public_key_hash)
+                .unwrap_or(Word::default());
+            inputs.extend_from_slice(hash.as_elements());
+        }
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_note_inputs_pads_n_to_a_full_word_and_zero_pads_unused_guardian_slots() {
+        let guardians = vec![Guardian::new(Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]))];
+        let threshold = GuardianThreshold::new(1, guardians);
+        let inputs = threshold.extend_note_inputs(vec![Felt::new(0); 4]);
+
+        assert_eq!(inputs.len(), 4 + 4 + MAX_GUARDIANS * 4);
+        assert_eq!(&inputs[4..8], &[Felt::new(1), Felt::new(0), Felt::new(0), Felt::new(0)]);
+        assert_eq!(&inputs[8..12], &[Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+        assert_eq!(&inputs[12..16], &[Felt::new(0); 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most")]
+    fn guardian_threshold_rejects_too_many_guardians() {
+        let guardians = (0..MAX_GUARDIANS + 1).map(|_| Guardian::new(Word::default())).collect();
+        GuardianThreshold::new(1, guardians);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1")]
+    fn guardian_threshold_rejects_zero_threshold_with_guardians() {
+        let guardians = vec![Guardian::new(Word::default())];
+        GuardianThreshold::new(0, guardians);
+    }
+}
@@ -0,0 +1,96 @@
+//! Deterministic key derivation for inheritance vault accounts.
+//!
+//! Keys handed to [`FilesystemKeyStore`] today come from `SecretKey::with_rng` seeded
+//! from the client's own RNG, so there is no way to reconstruct them if the keystore
+//! file is lost. `KeyManager` instead derives every account key from a single BIP39
+//! mnemonic, so a beneficiary who only holds the seed phrase can rebuild the owner and
+//! beneficiary keys on a fresh device.
+//!
+//! Derivation path:
+//! 1. `Mnemonic` (24 words, English wordlist) -> 64-byte seed via
+//!    PBKDF2-HMAC-SHA512, 2048 iterations, salt `"mnemonic"` + optional passphrase
+//!    (this matches BIP39's own seed derivation).
+//! 2. Per-account 32-byte child seed = first 32 bytes of HMAC-SHA512 keyed by
+//!    `index.to_be_bytes()`, computed over the master seed.
+//! 3. Child seed seeds a `StdRng`, which feeds `SecretKey::with_rng` to produce a
+//!    reproducible RpoFalcon512 key pair.
+
+use bip39::{Language, Mnemonic};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use rand::{rngs::StdRng, SeedableRng};
+use sha2::Sha512;
+
+use miden_client::{auth::AuthSecretKey, crypto::SecretKey, keystore::FilesystemKeyStore};
+
+const PBKDF2_ROUNDS: u32 = 2048;
+const PBKDF2_SALT_PREFIX: &str = "mnemonic";
+
+/// Derives and restores RpoFalcon512 keys from a single BIP39 mnemonic.
+pub struct KeyManager {
+    master_seed: [u8; 64],
+}
+
+impl KeyManager {
+    /// Generates a fresh 24-word mnemonic and the `KeyManager` derived from it.
+    pub fn generate(passphrase: &str) -> (Self, Mnemonic) {
+        let mnemonic = Mnemonic::generate_in(Language::English, 24).expect("24 is a valid word count");
+        let manager = Self::from_mnemonic(&mnemonic, passphrase);
+        (manager, mnemonic)
+    }
+
+    /// Rebuilds the master seed from an existing mnemonic, e.g. one entered by a
+    /// beneficiary during recovery.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        let mut master_seed = [0u8; 64];
+        let salt = format!("{PBKDF2_SALT_PREFIX}{passphrase}");
+        pbkdf2_hmac::<Sha512>(
+            mnemonic.to_string().as_bytes(),
+            salt.as_bytes(),
+            PBKDF2_ROUNDS,
+            &mut master_seed,
+        );
+        Self { master_seed }
+    }
+
+    /// Derives the child seed for account `index`: HMAC-SHA512 over the master seed,
+    /// keyed by the big-endian index, truncated to the 32 bytes `SecretKey::with_rng`
+    /// needs as an `StdRng` seed.
+    fn child_seed(&self, index: u32) -> [u8; 32] {
+        let mut mac = Hmac::<Sha512>::new_from_slice(&index.to_be_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(&self.master_seed);
+        let digest = mac.finalize().into_bytes();
+
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&digest[..32]);
+        seed
+    }
+
+    /// Deterministically derives the RpoFalcon512 key pair for `index`.
+    pub fn derive_key(&self, index: u32) -> SecretKey {
+        let mut rng = StdRng::from_seed(self.child_seed(index));
+        SecretKey::with_rng(&mut rng)
+    }
+
+    /// Re-derives the keys for account indices `0..count` and re-populates `keystore`,
+    /// so a beneficiary holding only the mnemonic can recover owner and beneficiary
+    /// accounts without access to the original keystore files.
+    pub fn restore(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        keystore: &FilesystemKeyStore<StdRng>,
+        count: u32,
+    ) -> Vec<SecretKey> {
+        let manager = Self::from_mnemonic(mnemonic, passphrase);
+        let mut keys = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let key = manager.derive_key(index);
+            keystore
+                .add_key(&AuthSecretKey::RpoFalcon512(key.clone()))
+                .unwrap();
+            keys.push(key);
+        }
+        keys
+    }
+}
@@ -0,0 +1,7 @@
+pub mod assets;
+pub mod faucet;
+pub mod guardian;
+pub mod key_manager;
+pub mod registry;
+pub mod secure_key;
+pub mod vault;
@@ -0,0 +1,77 @@
+//! Local registry of vaults created by the CLI, persisted to disk so a user can
+//! resume across sessions instead of losing track of note IDs, deadlines, and asset
+//! summaries the moment the process exits.
+
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+pub const REGISTRY_PATH: &str = "./vault_registry.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VaultRecord {
+    pub note_id: String,
+    pub owner: String,
+    pub beneficiary: String,
+    pub deadline: u64,
+    pub asset_summary: String,
+    /// Hex-encoded serialized `Note`, so `refresh`/`claim` can rebuild the exact note
+    /// to consume without needing a live client-side note lookup.
+    pub note_hex: String,
+    /// Index into the session's `KeyManager` for the owner's reclaim key, so
+    /// `refresh` can re-derive it without the caller re-entering a mnemonic.
+    pub owner_key_index: u32,
+    /// Minimum number of guardian signatures (M) configured for early release.
+    pub guardian_threshold: u32,
+    /// Indices into the session's `KeyManager` for this vault's guardian keys, so
+    /// `refresh` can rebuild the exact same `GuardianThreshold` for the re-emitted
+    /// note instead of silently dropping back to "no guardians".
+    pub guardian_key_indices: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TxHistoryEntry {
+    pub tx_id: String,
+    pub kind: String,
+    pub note_id: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Registry {
+    pub vaults: Vec<VaultRecord>,
+    pub history: Vec<TxHistoryEntry>,
+}
+
+impl Registry {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) {
+        let contents = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    pub fn record_vault(&mut self, record: VaultRecord) {
+        self.vaults.push(record);
+    }
+
+    /// Replaces the registry entry for `old_note_id` (if any) with `new_record`,
+    /// keeping the registry in sync after `refresh_vault` re-emits a note under a
+    /// new ID.
+    pub fn replace_vault(&mut self, old_note_id: &str, new_record: VaultRecord) {
+        self.vaults.retain(|v| v.note_id != old_note_id);
+        self.vaults.push(new_record);
+    }
+
+    pub fn record_tx(&mut self, tx_id: String, kind: &str, note_id: &str) {
+        self.history.push(TxHistoryEntry { tx_id, kind: kind.to_string(), note_id: note_id.to_string() });
+    }
+
+    pub fn find_vault(&self, note_id: &str) -> Option<&VaultRecord> {
+        self.vaults.iter().find(|v| v.note_id == note_id)
+    }
+}
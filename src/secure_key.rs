@@ -0,0 +1,52 @@
+//! Zeroizing storage for vault key material.
+//!
+//! `KeyManager::derive_key` and `keystore.add_key` both hand back or copy a
+//! `SecretKey` by value, which otherwise lingers in process memory for as long as
+//! that copy is alive. `ZeroizingSecretKey` keeps the key's serialized bytes in a
+//! `Zeroizing<Vec<u8>>`, so that buffer is wiped the moment it drops, and exposes only
+//! narrow, task-specific methods (`public_key_hash`, `sign`, `register_with`) instead
+//! of a general `expose()` -- each reconstructs the real `SecretKey` for the single
+//! call it backs and lets it drop immediately after, rather than handing an
+//! unprotected copy out to the caller to hold onto. Note that upstream `SecretKey`
+//! itself doesn't implement `Zeroize`, so this only bounds the exposure window to one
+//! call; it can't force that reconstructed copy's memory to be wiped on drop either.
+
+use miden_client::{
+    auth::AuthSecretKey,
+    crypto::{dsa::rpo_falcon512::Signature, SecretKey},
+    keystore::FilesystemKeyStore,
+    utils::{Deserializable, Serializable},
+    Word,
+};
+use rand::rngs::StdRng;
+use zeroize::Zeroizing;
+
+pub struct ZeroizingSecretKey {
+    bytes: Zeroizing<Vec<u8>>,
+}
+
+impl ZeroizingSecretKey {
+    pub fn new(key: SecretKey) -> Self {
+        Self { bytes: Zeroizing::new(key.to_bytes()) }
+    }
+
+    fn expose(&self) -> SecretKey {
+        SecretKey::read_from_bytes(&self.bytes).expect("serialized key material is well-formed")
+    }
+
+    /// The public key hash derived from the wrapped key, for embedding in note inputs.
+    pub fn public_key_hash(&self) -> Word {
+        self.expose().public_key().into()
+    }
+
+    /// Signs `message`, reconstructing the key only for this call.
+    pub fn sign(&self, message: Word) -> Signature {
+        self.expose().sign(message)
+    }
+
+    /// Registers this key with `keystore` so the executor can resolve signatures for
+    /// it, reconstructing the key only for the registration call.
+    pub fn register_with(&self, keystore: &FilesystemKeyStore<StdRng>) {
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(self.expose())).unwrap();
+    }
+}
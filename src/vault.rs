@@ -0,0 +1,114 @@
+//! Vault note construction and the owner's reclaim/refresh path.
+//!
+//! `masm/inheritance_vault_note.masm` gives the owner a way to pull the note back
+//! before the deadline by proving ownership, instead of only ever handing control to
+//! the beneficiary once the timer (or guardian consensus) fires. `refresh_vault` wraps
+//! that reclaim in the same transaction flow used to create the vault in the first
+//! place, so a periodically-online owner can keep pushing the deadline forward and the
+//! beneficiary only ever succeeds once the owner truly stops checking in.
+
+use std::{fs, path::Path};
+
+use miden_client::{
+    account::AccountId,
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{
+        Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteInputs, NoteMetadata,
+        NoteRecipient, NoteScript, NoteTag, NoteType,
+    },
+    transaction::{OutputNote, TransactionKernel, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+use rand::rngs::StdRng;
+
+use crate::{guardian::GuardianThreshold, secure_key::ZeroizingSecretKey};
+
+const NOTE_SCRIPT_PATH: &str = "masm/inheritance_vault_note.masm";
+
+/// The parts of a vault note that stay fixed across `refresh_vault` calls; only the
+/// deadline and serial number change from one version of the note to the next.
+pub struct VaultConfig {
+    pub owner: AccountId,
+    pub beneficiary: AccountId,
+    pub owner_pubkey_hash: Word,
+    pub guardian_threshold: GuardianThreshold,
+    /// The full asset bundle the beneficiary receives, built with
+    /// [`crate::assets::VaultAssetsBuilder`] so a vault can cover a mix of fungible
+    /// faucets and non-fungible assets, not just a single token.
+    pub assets: NoteAssets,
+}
+
+/// Builds (but does not submit) a vault note that unlocks at `deadline`.
+pub fn build_vault_note(client: &mut Client, config: &VaultConfig, deadline: u64) -> Note {
+    let assembler = TransactionKernel::assembler().with_debug_mode(true);
+    let note_code = fs::read_to_string(Path::new(NOTE_SCRIPT_PATH)).unwrap();
+    let note_script = NoteScript::compile(note_code, assembler).unwrap();
+
+    // Word-aligned so the note script can `mem_loadw` each word back individually
+    // instead of juggling two dozen stack slots: word 0 is
+    // [deadline, ben_suffix, ben_prefix, M], word 1 is [N, 0, 0, 0], words 2..4 are
+    // the (possibly zero-padded) guardian hashes, and word 5 is the owner hash.
+    let mut inputs = vec![
+        Felt::new(deadline),
+        config.beneficiary.suffix(),
+        config.beneficiary.prefix().as_felt(),
+        Felt::new(config.guardian_threshold.threshold as u64),
+    ];
+    inputs = config.guardian_threshold.extend_note_inputs(inputs);
+    inputs.extend_from_slice(config.owner_pubkey_hash.as_elements());
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+
+    let serial_num = client.rng().draw_word();
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+    let tag = NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local).unwrap();
+    let metadata = NoteMetadata::new(
+        config.owner,
+        NoteType::Public,
+        tag,
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )
+    .unwrap();
+
+    Note::new(config.assets.clone(), metadata, recipient)
+}
+
+/// Reclaims `old_note` as the owner by proving ownership (a signature over the note's
+/// serial number), then re-emits an identical note with `new_deadline`. Call this
+/// periodically while the owner is still alive and checking in; the beneficiary's
+/// claim only ever succeeds once the owner stops refreshing the vault.
+///
+/// `owner_key` is registered with `keystore` so the executor can resolve the signature
+/// the note script's `exec.rpo_falcon512::verify` call needs -- the reclaim note args
+/// carry no signature bytes at all, since `NoteArgs` is a single `Word`, far too small
+/// to hold one.
+pub async fn refresh_vault(
+    client: &mut Client,
+    keystore: &FilesystemKeyStore<StdRng>,
+    owner_key: &ZeroizingSecretKey,
+    config: &VaultConfig,
+    old_note: Note,
+    new_deadline: u64,
+) -> Result<Note, ClientError> {
+    owner_key.register_with(keystore);
+
+    let reclaim_request = TransactionRequestBuilder::new()
+        .with_unauthenticated_input_notes([(old_note, None)])
+        .build()
+        .unwrap();
+    let tx_result = client.new_transaction(config.owner, reclaim_request).await?;
+    client.submit_transaction(tx_result).await?;
+    client.sync_state().await?;
+
+    let new_note = build_vault_note(client, config, new_deadline);
+    let note_request = TransactionRequestBuilder::new()
+        .with_own_output_notes(vec![OutputNote::Full(new_note.clone())])
+        .build()
+        .unwrap();
+    let tx_result = client.new_transaction(config.owner, note_request).await?;
+    client.submit_transaction(tx_result).await?;
+    client.sync_state().await?;
+
+    Ok(new_note)
+}
@@ -0,0 +1,288 @@
+//! End-to-end coverage for `masm/inheritance_vault_note.masm`, run against a live local
+//! Miden test node (the same `localhost:57291` endpoint `src/bin/bench.rs` already
+//! assumes is up) rather than as a pure-Rust unit test. Every other test in this crate
+//! (`src/assets.rs`, `src/guardian.rs`) only exercises plain Rust helper structs; none
+//! of them actually assemble or execute the note script, which is exactly how this
+//! series shipped a non-terminating loop, a stuck asset pointer, an unconditionally-true
+//! guardian gate, and swapped identity-check operands without anything catching it.
+//! These tests build real vaults, submit real claim/reclaim transactions through
+//! `Client::new_transaction` + `submit_transaction`, and assert on whether the note
+//! script's own `assert.err` calls let the transaction through -- the only way to know
+//! the script actually does what its comments say.
+//!
+//! Run with a local test node up (see the Miden client docs for `miden-node`); there's
+//! no mocked/in-process processor harness in this crate to fall back to.
+
+use std::{sync::Arc, time::Duration};
+
+use miden_client::{
+    builder::ClientBuilder,
+    keystore::FilesystemKeyStore,
+    rpc::{Endpoint, TonicRpcClient},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError,
+};
+use miden_client_tools::create_basic_account;
+
+use miden_inheritance_vaults::{
+    assets::VaultAssetsBuilder,
+    faucet::{FaucetService, MintOutcome, RateLimitConfig},
+    guardian::{Guardian, GuardianThreshold},
+    key_manager::KeyManager,
+    secure_key::ZeroizingSecretKey,
+    vault::{build_vault_note, refresh_vault, VaultConfig},
+};
+
+async fn test_client() -> Result<(Client, FilesystemKeyStore<rand::rngs::StdRng>), ClientError> {
+    let endpoint = Endpoint::new("http".to_string(), "localhost".to_string(), Some(57291));
+    let rpc_api = Arc::new(TonicRpcClient::new(&endpoint, 10_000));
+    let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+    let mut client = ClientBuilder::new()
+        .with_rpc(rpc_api)
+        .with_filesystem_keystore("./keystore")
+        .in_debug_mode(true)
+        .build()
+        .await?;
+    client.sync_state().await?;
+    Ok((client, keystore))
+}
+
+async fn deploy_test_faucet(
+    client: &mut Client,
+    keystore: FilesystemKeyStore<rand::rngs::StdRng>,
+    key_manager: &KeyManager,
+) -> FaucetService {
+    let limits = RateLimitConfig { max_amount_per_request: u64::MAX, cooldown: Duration::from_secs(0) };
+    FaucetService::deploy(client, keystore, key_manager.derive_key(0), limits, "./test_faucet_state.json")
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn deadline_branch_releases_assets_once_block_number_catches_up() {
+    let (mut client, keystore) = test_client().await.unwrap();
+    let (key_manager, _mnemonic) = KeyManager::generate("");
+    let mut faucet = deploy_test_faucet(&mut client, keystore.clone(), &key_manager).await;
+
+    let (owner, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    let (beneficiary, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    faucet.request_mint(&mut client, &owner, 10).await.unwrap();
+
+    let owner_key = ZeroizingSecretKey::new(key_manager.derive_key(1));
+    let sync = client.sync_state().await.unwrap();
+    // A deadline one block behind the current tip: the note should be claimable
+    // immediately without any guardian involvement.
+    let deadline = sync.block_num.as_u64().saturating_sub(1);
+
+    let mut assets_builder = VaultAssetsBuilder::new();
+    assets_builder.add_fungible(faucet.account.id(), 10).unwrap();
+    let config = VaultConfig {
+        owner: owner.id(),
+        beneficiary: beneficiary.id(),
+        owner_pubkey_hash: owner_key.public_key_hash(),
+        guardian_threshold: GuardianThreshold::new(0, vec![]),
+        assets: assets_builder.build(),
+    };
+
+    let note = build_vault_note(&mut client, &config, deadline);
+    let create_request =
+        TransactionRequestBuilder::new().with_own_output_notes(vec![OutputNote::Full(note.clone())]).build().unwrap();
+    let tx_result = client.new_transaction(owner.id(), create_request).await.unwrap();
+    client.submit_transaction(tx_result).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let claim_request =
+        TransactionRequestBuilder::new().with_unauthenticated_input_notes([(note, None)]).build().unwrap();
+    let tx_result = client.new_transaction(beneficiary.id(), claim_request).await;
+    assert!(tx_result.is_ok(), "beneficiary claim past the deadline should succeed: {tx_result:?}");
+    client.submit_transaction(tx_result.unwrap()).await.unwrap();
+}
+
+#[tokio::test]
+async fn guardian_branch_releases_before_the_deadline_once_m_of_n_sign() {
+    let (mut client, keystore) = test_client().await.unwrap();
+    let (key_manager, _mnemonic) = KeyManager::generate("");
+    let mut faucet = deploy_test_faucet(&mut client, keystore.clone(), &key_manager).await;
+
+    let (owner, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    let (beneficiary, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    faucet.request_mint(&mut client, &owner, 10).await.unwrap();
+
+    let owner_key = ZeroizingSecretKey::new(key_manager.derive_key(1));
+    let guardian_keys: Vec<ZeroizingSecretKey> =
+        (200..203).map(|index| ZeroizingSecretKey::new(key_manager.derive_key(index))).collect();
+    // Only register 2 of the 3 guardians with the keystore -- exactly the threshold --
+    // so the test also proves unregistered guardian slots are skipped rather than
+    // faulting the whole claim.
+    guardian_keys[0].register_with(&keystore);
+    guardian_keys[1].register_with(&keystore);
+    let guardians: Vec<Guardian> =
+        guardian_keys.iter().map(|key| Guardian::new(key.public_key_hash())).collect();
+
+    let sync = client.sync_state().await.unwrap();
+    // Deadline far in the future: only the guardian branch can release this note.
+    let deadline = sync.block_num.as_u64() + 10_000;
+
+    let mut assets_builder = VaultAssetsBuilder::new();
+    assets_builder.add_fungible(faucet.account.id(), 10).unwrap();
+    let config = VaultConfig {
+        owner: owner.id(),
+        beneficiary: beneficiary.id(),
+        owner_pubkey_hash: owner_key.public_key_hash(),
+        guardian_threshold: GuardianThreshold::new(2, guardians),
+        assets: assets_builder.build(),
+    };
+
+    let note = build_vault_note(&mut client, &config, deadline);
+    let create_request =
+        TransactionRequestBuilder::new().with_own_output_notes(vec![OutputNote::Full(note.clone())]).build().unwrap();
+    let tx_result = client.new_transaction(owner.id(), create_request).await.unwrap();
+    client.submit_transaction(tx_result).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let claim_request =
+        TransactionRequestBuilder::new().with_unauthenticated_input_notes([(note, None)]).build().unwrap();
+    let tx_result = client.new_transaction(beneficiary.id(), claim_request).await;
+    assert!(tx_result.is_ok(), "2-of-3 guardian signatures should release the vault early: {tx_result:?}");
+    client.submit_transaction(tx_result.unwrap()).await.unwrap();
+}
+
+#[tokio::test]
+async fn guardian_branch_rejects_claim_when_fewer_than_m_guardians_are_registered() {
+    let (mut client, keystore) = test_client().await.unwrap();
+    let (key_manager, _mnemonic) = KeyManager::generate("");
+    let mut faucet = deploy_test_faucet(&mut client, keystore.clone(), &key_manager).await;
+
+    let (owner, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    let (beneficiary, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    faucet.request_mint(&mut client, &owner, 10).await.unwrap();
+
+    let owner_key = ZeroizingSecretKey::new(key_manager.derive_key(1));
+    let guardian_keys: Vec<ZeroizingSecretKey> =
+        (210..213).map(|index| ZeroizingSecretKey::new(key_manager.derive_key(index))).collect();
+    // Only 1 of the 3 guardians is registered, short of the threshold of 2.
+    guardian_keys[0].register_with(&keystore);
+    let guardians: Vec<Guardian> =
+        guardian_keys.iter().map(|key| Guardian::new(key.public_key_hash())).collect();
+
+    let sync = client.sync_state().await.unwrap();
+    let deadline = sync.block_num.as_u64() + 10_000;
+
+    let mut assets_builder = VaultAssetsBuilder::new();
+    assets_builder.add_fungible(faucet.account.id(), 10).unwrap();
+    let config = VaultConfig {
+        owner: owner.id(),
+        beneficiary: beneficiary.id(),
+        owner_pubkey_hash: owner_key.public_key_hash(),
+        guardian_threshold: GuardianThreshold::new(2, guardians),
+        assets: assets_builder.build(),
+    };
+
+    let note = build_vault_note(&mut client, &config, deadline);
+    let create_request =
+        TransactionRequestBuilder::new().with_own_output_notes(vec![OutputNote::Full(note.clone())]).build().unwrap();
+    let tx_result = client.new_transaction(owner.id(), create_request).await.unwrap();
+    client.submit_transaction(tx_result).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let claim_request =
+        TransactionRequestBuilder::new().with_unauthenticated_input_notes([(note, None)]).build().unwrap();
+    let tx_result = client.new_transaction(beneficiary.id(), claim_request).await;
+    assert!(tx_result.is_err(), "a claim with only 1 of 2 required guardian signatures must fail");
+}
+
+#[tokio::test]
+async fn owner_reclaim_via_refresh_vault_extends_the_deadline() {
+    let (mut client, keystore) = test_client().await.unwrap();
+    let (key_manager, _mnemonic) = KeyManager::generate("");
+    let mut faucet = deploy_test_faucet(&mut client, keystore.clone(), &key_manager).await;
+
+    let (owner, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    let (beneficiary, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    faucet.request_mint(&mut client, &owner, 10).await.unwrap();
+
+    let owner_key = ZeroizingSecretKey::new(key_manager.derive_key(1));
+    let sync = client.sync_state().await.unwrap();
+    let deadline = sync.block_num.as_u64() + 5;
+
+    let mut assets_builder = VaultAssetsBuilder::new();
+    assets_builder.add_fungible(faucet.account.id(), 10).unwrap();
+    let config = VaultConfig {
+        owner: owner.id(),
+        beneficiary: beneficiary.id(),
+        owner_pubkey_hash: owner_key.public_key_hash(),
+        guardian_threshold: GuardianThreshold::new(0, vec![]),
+        assets: assets_builder.build(),
+    };
+
+    let note = build_vault_note(&mut client, &config, deadline);
+    let create_request =
+        TransactionRequestBuilder::new().with_own_output_notes(vec![OutputNote::Full(note.clone())]).build().unwrap();
+    let tx_result = client.new_transaction(owner.id(), create_request).await.unwrap();
+    client.submit_transaction(tx_result).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let new_deadline = deadline + 1_000;
+    let refreshed = refresh_vault(&mut client, &keystore, &owner_key, &config, note, new_deadline)
+        .await
+        .expect("owner reclaim should succeed at any time, deadline or not");
+
+    // The beneficiary's original note is now spent; only the re-emitted note (with the
+    // pushed-out deadline) should still be claimable, and not yet -- its new deadline
+    // is far in the future and no guardians are configured.
+    let claim_request =
+        TransactionRequestBuilder::new().with_unauthenticated_input_notes([(refreshed, None)]).build().unwrap();
+    let tx_result = client.new_transaction(beneficiary.id(), claim_request).await;
+    assert!(tx_result.is_err(), "the refreshed note's deadline was pushed out and shouldn't be claimable yet");
+}
+
+#[tokio::test]
+async fn multi_asset_bundle_is_released_to_the_beneficiary_atomically() {
+    let (mut client, keystore) = test_client().await.unwrap();
+    let (key_manager, _mnemonic) = KeyManager::generate("");
+    let mut faucet_a = deploy_test_faucet(&mut client, keystore.clone(), &key_manager).await;
+    let mut faucet_b = FaucetService::deploy(
+        &mut client,
+        keystore.clone(),
+        key_manager.derive_key(50),
+        RateLimitConfig { max_amount_per_request: u64::MAX, cooldown: Duration::from_secs(0) },
+        "./test_faucet_state_b.json",
+    )
+    .await
+    .unwrap();
+
+    let (owner, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    let (beneficiary, _) = create_basic_account(&mut client, keystore.clone()).await.unwrap();
+    faucet_a.request_mint(&mut client, &owner, 7).await.unwrap();
+    if let MintOutcome::Rejected { reason_code, .. } = faucet_b.request_mint(&mut client, &owner, 3).await.unwrap() {
+        panic!("test faucet unexpectedly rejected mint (reason {reason_code})");
+    }
+
+    let owner_key = ZeroizingSecretKey::new(key_manager.derive_key(1));
+    let sync = client.sync_state().await.unwrap();
+    let deadline = sync.block_num.as_u64().saturating_sub(1);
+
+    let mut assets_builder = VaultAssetsBuilder::new();
+    assets_builder.add_fungible(faucet_a.account.id(), 7).unwrap();
+    assets_builder.add_fungible(faucet_b.account.id(), 3).unwrap();
+    let config = VaultConfig {
+        owner: owner.id(),
+        beneficiary: beneficiary.id(),
+        owner_pubkey_hash: owner_key.public_key_hash(),
+        guardian_threshold: GuardianThreshold::new(0, vec![]),
+        assets: assets_builder.build(),
+    };
+
+    let note = build_vault_note(&mut client, &config, deadline);
+    let create_request =
+        TransactionRequestBuilder::new().with_own_output_notes(vec![OutputNote::Full(note.clone())]).build().unwrap();
+    let tx_result = client.new_transaction(owner.id(), create_request).await.unwrap();
+    client.submit_transaction(tx_result).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let claim_request =
+        TransactionRequestBuilder::new().with_unauthenticated_input_notes([(note, None)]).build().unwrap();
+    let tx_result = client.new_transaction(beneficiary.id(), claim_request).await;
+    assert!(tx_result.is_ok(), "both assets in the bundle should release in one claim: {tx_result:?}");
+    client.submit_transaction(tx_result.unwrap()).await.unwrap();
+}